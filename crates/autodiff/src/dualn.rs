@@ -0,0 +1,180 @@
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Forward-mode dual number carrying `N` independent seed directions at
+/// once, so a whole gradient can be read off from a single evaluation
+/// instead of one `Dual` pass per input variable.
+#[derive(Debug, Clone, Copy)]
+pub struct DualN<const N: usize> {
+    real: f64,
+    dual: [f64; N],
+}
+
+impl<const N: usize> DualN<N> {
+    /// A variable seeded along direction `index` (its own derivative is 1,
+    /// every other tracked variable's is 0).
+    pub fn seed(real: f64, index: usize) -> Self {
+        let mut dual = [0.0; N];
+        dual[index] = 1.0;
+        Self { real, dual }
+    }
+
+    fn constant(real: f64) -> Self {
+        Self {
+            real,
+            dual: [0.0; N],
+        }
+    }
+
+    fn map(self, g: impl Fn(f64) -> f64, dg: f64) -> Self {
+        let mut dual = self.dual;
+        for d in &mut dual {
+            *d *= dg;
+        }
+        Self {
+            real: g(self.real),
+            dual,
+        }
+    }
+
+    pub fn exp(self) -> Self {
+        let r = self.real.exp();
+        self.map(|_| r, r)
+    }
+
+    pub fn sin(self) -> Self {
+        let dg = self.real.cos();
+        self.map(f64::sin, dg)
+    }
+
+    pub fn cos(self) -> Self {
+        let dg = -self.real.sin();
+        self.map(f64::cos, dg)
+    }
+
+    pub fn ln(self) -> Self {
+        let dg = 1.0 / self.real;
+        self.map(f64::ln, dg)
+    }
+}
+
+impl<const N: usize> From<f64> for DualN<N> {
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl<const N: usize> Add for DualN<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real + rhs.real,
+            dual: std::array::from_fn(|i| self.dual[i] + rhs.dual[i]),
+        }
+    }
+}
+
+impl<const N: usize> Sub for DualN<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real - rhs.real,
+            dual: std::array::from_fn(|i| self.dual[i] - rhs.dual[i]),
+        }
+    }
+}
+
+impl<const N: usize> Neg for DualN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut dual = self.dual;
+        for d in &mut dual {
+            *d = -*d;
+        }
+        Self {
+            real: -self.real,
+            dual,
+        }
+    }
+}
+
+impl<const N: usize> Mul for DualN<N> {
+    type Output = Self;
+
+    // The product rule legitimately mixes `+` and `*` here; this isn't a typo'd `Add` impl.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real * rhs.real,
+            dual: std::array::from_fn(|i| self.real * rhs.dual[i] + self.dual[i] * rhs.real),
+        }
+    }
+}
+
+impl<const N: usize> Div for DualN<N> {
+    type Output = Self;
+
+    // The quotient rule legitimately mixes `-` and `*` here; this isn't a typo'd `Sub` impl.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real / rhs.real,
+            dual: std::array::from_fn(|i| {
+                (self.dual[i] * rhs.real - self.real * rhs.dual[i]) / (rhs.real * rhs.real)
+            }),
+        }
+    }
+}
+
+impl<const N: usize> Display for DualN<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} + {:?}ε", self.real, self.dual)
+    }
+}
+
+/// Evaluates `f` at `point` and returns the full gradient in one pass, by
+/// seeding each coordinate of `point` along its own independent direction.
+pub fn grad<F, const N: usize>(f: F, point: [f64; N]) -> [f64; N]
+where
+    F: Fn([DualN<N>; N]) -> DualN<N>,
+{
+    let inputs = std::array::from_fn(|i| DualN::seed(point[i], i));
+    f(inputs).dual
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_approx_eq;
+
+    #[test]
+    fn grad_of_sum_of_two_variables() {
+        let g = grad(|[x, y]| x + y, [3.0, 4.0]);
+        assert_eq!(g, [1.0, 1.0]);
+    }
+
+    #[test]
+    fn grad_of_product_of_two_variables() {
+        let g = grad(|[x, y]| x * y, [3.0, 4.0]);
+        assert_eq!(g, [4.0, 3.0]);
+    }
+
+    #[test]
+    fn grad_of_three_variable_function() {
+        // f(x, y, z) = x*y + z
+        let g = grad(|[x, y, z]| x * y + z, [2.0, 3.0, 4.0]);
+        assert_eq!(g, [3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn grad_of_composite_function() {
+        // f(x, y) = exp(x*y), df/dx = y*exp(xy), df/dy = x*exp(xy)
+        let g = grad(|[x, y]| (x * y).exp(), [1.0, 2.0]);
+        let e2 = std::f64::consts::E.powf(2.0);
+        assert_approx_eq(g[0], 2.0 * e2);
+        assert_approx_eq(g[1], 1.0 * e2);
+    }
+}
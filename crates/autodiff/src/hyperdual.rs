@@ -0,0 +1,214 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Dual number over the algebra `ε1² = ε2² = 0, ε1·ε2 ≠ 0`, so a single
+/// evaluation carries both first derivatives (the `e1`/`e2` components) and
+/// the second derivative (the `e1e2` component) with no finite-difference
+/// error.
+#[derive(Debug, Clone, Copy)]
+pub struct HyperDual {
+    real: f64,
+    e1: f64,
+    e2: f64,
+    e1e2: f64,
+}
+
+impl HyperDual {
+    // Seeds both first-order directions at once so `e1e2` accumulates the
+    // second derivative in a single pass.
+    pub fn new(real: f64) -> Self {
+        Self {
+            real,
+            e1: 1.0,
+            e2: 1.0,
+            e1e2: 0.0,
+        }
+    }
+
+    fn map(self, g: f64, dg: f64, ddg: f64) -> Self {
+        Self {
+            real: g,
+            e1: self.e1 * dg,
+            e2: self.e2 * dg,
+            e1e2: self.e1e2 * dg + self.e1 * self.e2 * ddg,
+        }
+    }
+
+    pub fn exp(self) -> Self {
+        let g = self.real.exp();
+        self.map(g, g, g)
+    }
+
+    pub fn sin(self) -> Self {
+        self.map(self.real.sin(), self.real.cos(), -self.real.sin())
+    }
+
+    pub fn cos(self) -> Self {
+        self.map(self.real.cos(), -self.real.sin(), -self.real.cos())
+    }
+
+    pub fn ln(self) -> Self {
+        self.map(self.real.ln(), 1.0 / self.real, -1.0 / (self.real * self.real))
+    }
+}
+
+impl From<f64> for HyperDual {
+    fn from(value: f64) -> Self {
+        Self {
+            real: value,
+            e1: 0.0,
+            e2: 0.0,
+            e1e2: 0.0,
+        }
+    }
+}
+
+impl Add for HyperDual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real + rhs.real,
+            e1: self.e1 + rhs.e1,
+            e2: self.e2 + rhs.e2,
+            e1e2: self.e1e2 + rhs.e1e2,
+        }
+    }
+}
+
+impl Sub for HyperDual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            real: self.real - rhs.real,
+            e1: self.e1 - rhs.e1,
+            e2: self.e2 - rhs.e2,
+            e1e2: self.e1e2 - rhs.e1e2,
+        }
+    }
+}
+
+impl Neg for HyperDual {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            real: -self.real,
+            e1: -self.e1,
+            e2: -self.e2,
+            e1e2: -self.e1e2,
+        }
+    }
+}
+
+impl Mul for HyperDual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // (a + bε1 + cε2 + dε1ε2)(a' + b'ε1 + c'ε2 + d'ε1ε2), keeping terms up to ε1ε2
+        Self {
+            real: self.real * rhs.real,
+            e1: self.real * rhs.e1 + self.e1 * rhs.real,
+            e2: self.real * rhs.e2 + self.e2 * rhs.real,
+            e1e2: self.real * rhs.e1e2
+                + self.e1 * rhs.e2
+                + self.e2 * rhs.e1
+                + self.e1e2 * rhs.real,
+        }
+    }
+}
+
+impl Div for HyperDual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        // Multiply by the truncated series for 1/rhs.
+        let a = rhs.real;
+        let recip = Self {
+            real: 1.0 / a,
+            e1: -rhs.e1 / (a * a),
+            e2: -rhs.e2 / (a * a),
+            e1e2: -rhs.e1e2 / (a * a) + 2.0 * rhs.e1 * rhs.e2 / (a * a * a),
+        };
+        self * recip
+    }
+}
+
+/// Evaluates `f` at `x` and returns `(f(x), f'(x), f''(x))` from a single
+/// pass, by seeding both hyper-dual directions at `x`.
+pub fn diff2<T>(f: T, x: f64) -> (f64, f64, f64)
+where
+    T: Fn(HyperDual) -> HyperDual,
+{
+    let result = f(HyperDual::new(x));
+    (result.real, result.e1, result.e1e2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_approx_eq;
+
+    #[test]
+    fn diff2_of_square_function() {
+        // f(x) = x^2, f'(x) = 2x, f''(x) = 2
+        let (y, dy, ddy) = diff2(|x| x * x, 3.0);
+        assert_eq!(y, 9.0);
+        assert_eq!(dy, 6.0);
+        assert_eq!(ddy, 2.0);
+    }
+
+    #[test]
+    fn diff2_of_cube_function() {
+        // f(x) = x^3, f'(x) = 3x^2, f''(x) = 6x
+        let (y, dy, ddy) = diff2(|x| x * x * x, 2.0);
+        assert_eq!(y, 8.0);
+        assert_eq!(dy, 12.0);
+        assert_eq!(ddy, 12.0);
+    }
+
+    #[test]
+    fn diff2_of_exponential_function() {
+        // f(x) = exp(x), f'(x) = exp(x), f''(x) = exp(x)
+        let (y, dy, ddy) = diff2(|x| x.exp(), 1.0);
+        assert_approx_eq(y, std::f64::consts::E);
+        assert_approx_eq(dy, std::f64::consts::E);
+        assert_approx_eq(ddy, std::f64::consts::E);
+    }
+
+    #[test]
+    fn diff2_of_sine_function() {
+        // f(x) = sin(x), f'(x) = cos(x), f''(x) = -sin(x)
+        let (y, dy, ddy) = diff2(|x| x.sin(), 0.0);
+        assert_approx_eq(y, 0.0);
+        assert_approx_eq(dy, 1.0);
+        assert_approx_eq(ddy, 0.0);
+    }
+
+    #[test]
+    fn diff2_of_quotient_function() {
+        // f(x) = 1/x, f'(x) = -1/x^2, f''(x) = 2/x^3
+        let (y, dy, ddy) = diff2(|x| HyperDual::from(1.0) / x, 2.0);
+        assert_approx_eq(y, 0.5);
+        assert_approx_eq(dy, -0.25);
+        assert_approx_eq(ddy, 0.25);
+    }
+
+    #[test]
+    fn diff2_of_cosine_function() {
+        // f(x) = cos(x), f'(x) = -sin(x), f''(x) = -cos(x)
+        let (y, dy, ddy) = diff2(|x| x.cos(), 0.0);
+        assert_approx_eq(y, 1.0);
+        assert_approx_eq(dy, 0.0);
+        assert_approx_eq(ddy, -1.0);
+    }
+
+    #[test]
+    fn diff2_of_logarithm_function() {
+        // f(x) = ln(x), f'(x) = 1/x, f''(x) = -1/x^2
+        let (y, dy, ddy) = diff2(|x| x.ln(), 2.0);
+        assert_approx_eq(y, std::f64::consts::LN_2);
+        assert_approx_eq(dy, 0.5);
+        assert_approx_eq(ddy, -0.25);
+    }
+}
@@ -1,6 +1,16 @@
 use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
+mod dualn;
+mod hyperdual;
+mod tape;
+#[cfg(test)]
+mod test_support;
+
+pub use dualn::{grad, DualN};
+pub use hyperdual::{diff2, HyperDual};
+pub use tape::{Tape, Var};
+
 #[derive(Debug, Clone, Copy)]
 struct Dual {
     real: f64,
@@ -39,6 +49,75 @@ impl Dual {
             dual: self.dual / self.real,
         }
     }
+
+    fn tan(self) -> Self {
+        Self {
+            real: self.real.tan(),
+            dual: self.dual / (self.real.cos() * self.real.cos()),
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        let r = self.real.sqrt();
+        Self {
+            real: r,
+            dual: 0.5 * self.dual / r,
+        }
+    }
+
+    fn powf(self, p: f64) -> Self {
+        Self {
+            real: self.real.powf(p),
+            dual: p * self.real.powf(p - 1.0) * self.dual,
+        }
+    }
+
+    fn powi(self, p: i32) -> Self {
+        Self {
+            real: self.real.powi(p),
+            dual: if p == 0 {
+                0.0
+            } else {
+                p as f64 * self.real.powi(p - 1) * self.dual
+            },
+        }
+    }
+
+    fn tanh(self) -> Self {
+        let t = self.real.tanh();
+        Self {
+            real: t,
+            dual: self.dual * (1.0 - t * t),
+        }
+    }
+
+    fn atan(self) -> Self {
+        Self {
+            real: self.real.atan(),
+            dual: self.dual / (1.0 + self.real * self.real),
+        }
+    }
+
+    fn asin(self) -> Self {
+        Self {
+            real: self.real.asin(),
+            dual: self.dual / (1.0 - self.real * self.real).sqrt(),
+        }
+    }
+
+    fn acos(self) -> Self {
+        Self {
+            real: self.real.acos(),
+            dual: -self.dual / (1.0 - self.real * self.real).sqrt(),
+        }
+    }
+
+    fn abs(self) -> Self {
+        Self {
+            real: self.real.abs(),
+            dual: self.dual * self.real.signum(),
+        }
+    }
 }
 
 impl From<f64> for Dual {
@@ -124,6 +203,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::assert_approx_eq;
 
     #[test]
     fn diff_of_identify_function() {
@@ -173,16 +253,6 @@ mod tests {
         assert_eq!(dy, 2.0);
     }
 
-    fn assert_approx_eq(a: f64, b: f64) {
-        let tol = 1e-10;
-        assert!(
-            (a - b).abs() < tol,
-            "Expected {} to be approximately equal to {}",
-            a,
-            b
-        );
-    }
-
     #[test]
     fn test_of_subtraction_function() {
         let (y, dy) = diff(|x| x - Dual::from(1.0), 3.0);
@@ -266,6 +336,95 @@ mod tests {
         assert_approx_eq(dy, 0.0);
     }
 
+    #[test]
+    // f(x) = tan(x)
+    // f'(x) = 1/cos(x)^2
+    fn test_of_tangent_function() {
+        let (y, dy) = diff(|x| x.tan(), 1.0);
+        assert_approx_eq(y, 1.0_f64.tan());
+        assert_approx_eq(dy, 1.0 / (1.0_f64.cos() * 1.0_f64.cos()));
+    }
+
+    #[test]
+    // f(x) = sqrt(x)
+    // f'(x) = 1 / (2 sqrt(x))
+    fn test_of_square_root_function() {
+        let (y, dy) = diff(|x| x.sqrt(), 4.0);
+        assert_approx_eq(y, 2.0);
+        assert_approx_eq(dy, 0.25);
+    }
+
+    #[test]
+    // f(x) = x^2.5
+    // f'(x) = 2.5 x^1.5
+    fn test_of_powf_function() {
+        let (y, dy) = diff(|x| x.powf(2.5), 2.0);
+        assert_approx_eq(y, 2.0_f64.powf(2.5));
+        assert_approx_eq(dy, 2.5 * 2.0_f64.powf(1.5));
+    }
+
+    #[test]
+    // f(x) = x^3
+    // f'(x) = 3x^2
+    fn test_of_powi_function() {
+        let (y, dy) = diff(|x| x.powi(3), 2.0);
+        assert_approx_eq(y, 8.0);
+        assert_approx_eq(dy, 12.0);
+    }
+
+    #[test]
+    // f(x) = x^0 = 1, f'(x) = 0, including at x = 0
+    fn test_of_powi_zero_exponent_at_zero() {
+        let (y, dy) = diff(|x| x.powi(0), 0.0);
+        assert_approx_eq(y, 1.0);
+        assert_approx_eq(dy, 0.0);
+    }
+
+    #[test]
+    // f(x) = tanh(x)
+    // f'(x) = 1 - tanh(x)^2
+    fn test_of_hyperbolic_tangent_function() {
+        let (y, dy) = diff(|x| x.tanh(), 0.5);
+        assert_approx_eq(y, 0.5_f64.tanh());
+        assert_approx_eq(dy, 1.0 - 0.5_f64.tanh() * 0.5_f64.tanh());
+    }
+
+    #[test]
+    // f(x) = atan(x)
+    // f'(x) = 1 / (1 + x^2)
+    fn test_of_arctangent_function() {
+        let (y, dy) = diff(|x| x.atan(), 1.0);
+        assert_approx_eq(y, 1.0_f64.atan());
+        assert_approx_eq(dy, 0.5);
+    }
+
+    #[test]
+    // f(x) = asin(x)
+    // f'(x) = 1 / sqrt(1 - x^2)
+    fn test_of_arcsine_function() {
+        let (y, dy) = diff(|x| x.asin(), 0.5);
+        assert_approx_eq(y, 0.5_f64.asin());
+        assert_approx_eq(dy, 1.0 / (1.0 - 0.25_f64).sqrt());
+    }
+
+    #[test]
+    // f(x) = acos(x)
+    // f'(x) = -1 / sqrt(1 - x^2)
+    fn test_of_arccosine_function() {
+        let (y, dy) = diff(|x| x.acos(), 0.5);
+        assert_approx_eq(y, 0.5_f64.acos());
+        assert_approx_eq(dy, -1.0 / (1.0 - 0.25_f64).sqrt());
+    }
+
+    #[test]
+    // f(x) = |x|
+    // f'(x) = sign(x)
+    fn test_of_absolute_value_function() {
+        let (y, dy) = diff(|x| x.abs(), -3.0);
+        assert_approx_eq(y, 3.0);
+        assert_approx_eq(dy, -1.0);
+    }
+
     #[test]
     // f(x) = ln(x)
     // f'(x) = 1/x
@@ -0,0 +1,259 @@
+use std::cell::RefCell;
+
+// Sentinel used in `Node::parents` for a slot with no parent (a leaf, or the
+// second parent of a unary operation).
+const NO_PARENT: usize = usize::MAX;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    parents: [usize; 2],
+    locals: [f64; 2],
+}
+
+/// A Wengert list: records every operation performed on its `Var`s so that
+/// `grad` can later walk the list backwards and accumulate adjoints in a
+/// single pass, rather than re-running forward-mode once per input.
+#[derive(Debug, Default)]
+pub struct Tape {
+    nodes: RefCell<Vec<Node>>,
+}
+
+impl Tape {
+    pub fn new() -> Self {
+        Self {
+            nodes: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Introduces a new independent variable at the given value.
+    pub fn var(&self, value: f64) -> Var<'_> {
+        self.push(value, [NO_PARENT, NO_PARENT], [0.0, 0.0])
+    }
+
+    fn push(&self, value: f64, parents: [usize; 2], locals: [f64; 2]) -> Var<'_> {
+        let mut nodes = self.nodes.borrow_mut();
+        let index = nodes.len();
+        nodes.push(Node { parents, locals });
+        Var {
+            tape: self,
+            index,
+            value,
+        }
+    }
+
+    /// Runs the backward pass for `output` and returns the adjoint of every
+    /// node on the tape, indexed the same way `Var::index` is, so the
+    /// gradient of any input variable is `adjoints[var.index]`.
+    pub fn grad(&self, output: Var) -> Vec<f64> {
+        assert!(
+            std::ptr::eq(self, output.tape),
+            "Var passed to Tape::grad was not created by this Tape"
+        );
+
+        let nodes = self.nodes.borrow();
+        let mut adjoints = vec![0.0; nodes.len()];
+        adjoints[output.index] = 1.0;
+
+        for i in (0..nodes.len()).rev() {
+            let adj = adjoints[i];
+            if adj == 0.0 {
+                continue;
+            }
+            let node = nodes[i];
+            for k in 0..2 {
+                let parent = node.parents[k];
+                if parent != NO_PARENT {
+                    adjoints[parent] += node.locals[k] * adj;
+                }
+            }
+        }
+
+        adjoints
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Var<'a> {
+    tape: &'a Tape,
+    index: usize,
+    value: f64,
+}
+
+impl<'a> Var<'a> {
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    pub fn value(self) -> f64 {
+        self.value
+    }
+
+    pub fn exp(self) -> Self {
+        let value = self.value.exp();
+        self.tape
+            .push(value, [self.index, NO_PARENT], [value, 0.0])
+    }
+
+    pub fn sin(self) -> Self {
+        let value = self.value.sin();
+        self.tape
+            .push(value, [self.index, NO_PARENT], [self.value.cos(), 0.0])
+    }
+
+    pub fn cos(self) -> Self {
+        let value = self.value.cos();
+        self.tape
+            .push(value, [self.index, NO_PARENT], [-self.value.sin(), 0.0])
+    }
+
+    pub fn ln(self) -> Self {
+        let value = self.value.ln();
+        self.tape
+            .push(value, [self.index, NO_PARENT], [1.0 / self.value, 0.0])
+    }
+}
+
+impl<'a> std::ops::Add for Var<'a> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.tape.push(
+            self.value + rhs.value,
+            [self.index, rhs.index],
+            [1.0, 1.0],
+        )
+    }
+}
+
+impl<'a> std::ops::Sub for Var<'a> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.tape.push(
+            self.value - rhs.value,
+            [self.index, rhs.index],
+            [1.0, -1.0],
+        )
+    }
+}
+
+impl<'a> std::ops::Neg for Var<'a> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.tape.push(-self.value, [self.index, NO_PARENT], [-1.0, 0.0])
+    }
+}
+
+impl<'a> std::ops::Mul for Var<'a> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // d(ab)/da = b, d(ab)/db = a
+        self.tape.push(
+            self.value * rhs.value,
+            [self.index, rhs.index],
+            [rhs.value, self.value],
+        )
+    }
+}
+
+impl<'a> std::ops::Div for Var<'a> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        // d(a/b)/da = 1/b, d(a/b)/db = -a/b^2
+        self.tape.push(
+            self.value / rhs.value,
+            [self.index, rhs.index],
+            [1.0 / rhs.value, -self.value / (rhs.value * rhs.value)],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_approx_eq;
+
+    #[test]
+    fn grad_of_sum() {
+        let tape = Tape::new();
+        let x = tape.var(2.0);
+        let y = tape.var(3.0);
+        let z = x + y;
+
+        let adjoints = tape.grad(z);
+        assert_eq!(z.value, 5.0);
+        assert_eq!(adjoints[x.index()], 1.0);
+        assert_eq!(adjoints[y.index()], 1.0);
+    }
+
+    #[test]
+    fn grad_of_product() {
+        let tape = Tape::new();
+        let x = tape.var(2.0);
+        let y = tape.var(3.0);
+        let z = x * y;
+
+        let adjoints = tape.grad(z);
+        assert_eq!(z.value, 6.0);
+        assert_eq!(adjoints[x.index()], 3.0);
+        assert_eq!(adjoints[y.index()], 2.0);
+    }
+
+    #[test]
+    fn grad_of_many_input_function() {
+        // f(x, y, z) = x*y + z
+        let tape = Tape::new();
+        let x = tape.var(2.0);
+        let y = tape.var(3.0);
+        let z = tape.var(4.0);
+        let f = x * y + z;
+
+        let adjoints = tape.grad(f);
+        assert_eq!(f.value, 10.0);
+        assert_eq!(adjoints[x.index()], 3.0);
+        assert_eq!(adjoints[y.index()], 2.0);
+        assert_eq!(adjoints[z.index()], 1.0);
+    }
+
+    #[test]
+    fn grad_of_composite_function() {
+        // f(x) = exp(x^3 + x), f'(x) = exp(x^3 + x) * (3x^2 + 1)
+        let tape = Tape::new();
+        let x = tape.var(2.0);
+        let f = (x * x * x + x).exp();
+
+        let adjoints = tape.grad(f);
+        let expected_y = (8.0_f64 + 2.0).exp();
+        let expected_dy = expected_y * (3.0 * 4.0 + 1.0);
+
+        assert_approx_eq(f.value, expected_y);
+        assert_approx_eq(adjoints[x.index()], expected_dy);
+    }
+
+    #[test]
+    fn grad_of_quotient() {
+        let tape = Tape::new();
+        let x = tape.var(1.0);
+        let y = tape.var(2.0);
+        let f = x / y;
+
+        let adjoints = tape.grad(f);
+        assert_approx_eq(f.value, 0.5);
+        assert_approx_eq(adjoints[x.index()], 0.5);
+        assert_approx_eq(adjoints[y.index()], -0.25);
+    }
+
+    #[test]
+    #[should_panic(expected = "was not created by this Tape")]
+    fn grad_rejects_var_from_another_tape() {
+        let tape1 = Tape::new();
+        let tape2 = Tape::new();
+        let x = tape1.var(1.0);
+        let _y = tape2.var(2.0);
+
+        tape2.grad(x);
+    }
+}
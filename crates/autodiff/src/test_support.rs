@@ -0,0 +1,9 @@
+pub(crate) fn assert_approx_eq(a: f64, b: f64) {
+    let tol = 1e-9;
+    assert!(
+        (a - b).abs() < tol,
+        "Expected {} to be approximately equal to {}",
+        a,
+        b
+    );
+}
@@ -0,0 +1,44 @@
+/// Behavior when `,` is executed but the input stream is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eof {
+    /// Leave the current cell's value unchanged.
+    Unchanged,
+    /// Set the current cell to 0.
+    Zero,
+    /// Set the current cell to 255 (-1 as an unsigned byte).
+    NegativeOne,
+}
+
+/// Behavior when `<` would move the pointer below cell 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Underflow {
+    /// Stay at cell 0, the same as the fixed-behavior `run`.
+    Pin,
+    /// Treat it as an error.
+    Error,
+    /// Wrap around to the last cell of the tape.
+    Wrap,
+}
+
+/// Tunables for `run_with_config`. `Config::default()` reproduces the
+/// behavior of the plain `run`, except that EOF on `,` no longer errors
+/// (it sets the cell to 255, the conventional EOF sentinel). Like `run`,
+/// the tape stays fixed at 1024 cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub eof: Eof,
+    pub underflow: Underflow,
+    pub growable_tape: bool,
+    pub step_limit: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            eof: Eof::NegativeOne,
+            underflow: Underflow::Pin,
+            growable_tape: false,
+            step_limit: None,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt::{self, Display};
+
+use crate::config::{Config, Eof, Underflow};
+use crate::State;
+
+/// A single Brainfuck operation after parsing: runs of `+`/`-` and `>`/`<`
+/// are folded into one instruction each, and the `[-]`/`[+]` clear idiom is
+/// recognized as `SetZero`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instr {
+    Add(i8),
+    Move(isize),
+    Output,
+    Input,
+    SetZero,
+    Loop(Vec<Instr>),
+}
+
+impl Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instr::Add(n) if *n >= 0 => write!(f, "{}", "+".repeat(*n as usize)),
+            // Widen before negating: `n` can be `i8::MIN`, which has no positive `i8` counterpart.
+            Instr::Add(n) => write!(f, "{}", "-".repeat((-(*n as i32)) as usize)),
+            Instr::Move(n) if *n >= 0 => write!(f, "{}", ">".repeat(*n as usize)),
+            Instr::Move(n) => write!(f, "{}", "<".repeat((-*n) as usize)),
+            Instr::Output => write!(f, "."),
+            Instr::Input => write!(f, ","),
+            Instr::SetZero => write!(f, "[-]"),
+            Instr::Loop(body) => {
+                write!(f, "[")?;
+                for instr in body {
+                    write!(f, "{}", instr)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+pub fn parse(programs: &str) -> Result<Vec<Instr>, String> {
+    let chars: Vec<char> = programs.chars().collect();
+    let mut pos = 0;
+    let instrs = parse_block(&chars, &mut pos)?;
+    if pos < chars.len() {
+        return Err("`]`に対応する`[`が見つかりません。".into());
+    }
+    Ok(instrs)
+}
+
+fn parse_block(chars: &[char], pos: &mut usize) -> Result<Vec<Instr>, String> {
+    let mut instrs = Vec::new();
+
+    while *pos < chars.len() {
+        match chars[*pos] {
+            ']' => break,
+            '+' | '-' => {
+                let mut count: i32 = 0;
+                while *pos < chars.len() && matches!(chars[*pos], '+' | '-') {
+                    count += if chars[*pos] == '+' { 1 } else { -1 };
+                    *pos += 1;
+                }
+                instrs.push(Instr::Add(count as i8));
+            }
+            '>' | '<' => {
+                let mut count: isize = 0;
+                while *pos < chars.len() && matches!(chars[*pos], '>' | '<') {
+                    count += if chars[*pos] == '>' { 1 } else { -1 };
+                    *pos += 1;
+                }
+                instrs.push(Instr::Move(count));
+            }
+            '.' => {
+                instrs.push(Instr::Output);
+                *pos += 1;
+            }
+            ',' => {
+                instrs.push(Instr::Input);
+                *pos += 1;
+            }
+            '[' => {
+                *pos += 1;
+                let body = parse_block(chars, pos)?;
+                if *pos >= chars.len() {
+                    return Err("`[`に対応する`]`が見つかりません。".into());
+                }
+                *pos += 1; // consume the matching `]`
+                instrs.push(fold_loop(body));
+            }
+            _ => {
+                *pos += 1;
+            }
+        }
+    }
+
+    Ok(instrs)
+}
+
+fn fold_loop(body: Vec<Instr>) -> Instr {
+    if matches!(body.as_slice(), [Instr::Add(1)] | [Instr::Add(-1)]) {
+        Instr::SetZero
+    } else {
+        Instr::Loop(body)
+    }
+}
+
+pub(crate) fn eval(instrs: &[Instr], state: &mut State) -> Result<(), String> {
+    for instr in instrs {
+        match instr {
+            Instr::Add(n) => {
+                state.memory[state.ptr] = state.memory[state.ptr].wrapping_add(*n as u8);
+            }
+            Instr::Move(n) if *n >= 0 => {
+                state.ptr = state.ptr.wrapping_add(*n as usize);
+            }
+            Instr::Move(n) => {
+                state.ptr = state.ptr.saturating_sub((-*n) as usize);
+            }
+            Instr::Output => state.output.push(state.memory[state.ptr].into()),
+            Instr::Input => {
+                state.memory[state.ptr] =
+                    state.input.next().ok_or("入力が与えられませんでした。")? as u8;
+            }
+            Instr::SetZero => state.memory[state.ptr] = 0,
+            Instr::Loop(body) => {
+                while state.memory[state.ptr] != 0 {
+                    eval(body, state)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn eval_with_config(
+    instrs: &[Instr],
+    state: &mut State,
+    config: &Config,
+    steps: &mut usize,
+) -> Result<(), String> {
+    for instr in instrs {
+        if let Some(limit) = config.step_limit {
+            if *steps >= limit {
+                return Err("最大ステップ数に達しました。".into());
+            }
+        }
+        *steps += 1;
+
+        match instr {
+            Instr::Add(n) => {
+                ensure_capacity(state, config);
+                state.memory[state.ptr] = state.memory[state.ptr].wrapping_add(*n as u8);
+            }
+            Instr::Move(n) => move_ptr(state, config, *n)?,
+            Instr::Output => {
+                ensure_capacity(state, config);
+                state.output.push(state.memory[state.ptr].into());
+            }
+            Instr::Input => {
+                ensure_capacity(state, config);
+                state.memory[state.ptr] = match state.input.next() {
+                    Some(c) => c as u8,
+                    None => match config.eof {
+                        Eof::Unchanged => state.memory[state.ptr],
+                        Eof::Zero => 0,
+                        Eof::NegativeOne => 255,
+                    },
+                };
+            }
+            Instr::SetZero => {
+                ensure_capacity(state, config);
+                state.memory[state.ptr] = 0;
+            }
+            Instr::Loop(body) => {
+                ensure_capacity(state, config);
+                while state.memory[state.ptr] != 0 {
+                    eval_with_config(body, state, config, steps)?;
+                    ensure_capacity(state, config);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn ensure_capacity(state: &mut State, config: &Config) {
+    if config.growable_tape && state.ptr >= state.memory.len() {
+        state.memory.resize(state.ptr + 1, 0);
+    }
+}
+
+fn move_ptr(state: &mut State, config: &Config, delta: isize) -> Result<(), String> {
+    if delta >= 0 {
+        state.ptr = state.ptr.wrapping_add(delta as usize);
+        return Ok(());
+    }
+
+    let amount = (-delta) as usize;
+    if amount <= state.ptr {
+        state.ptr -= amount;
+        return Ok(());
+    }
+
+    match config.underflow {
+        Underflow::Pin => state.ptr = 0,
+        Underflow::Error => return Err("ポインタが0未満になりました。".into()),
+        Underflow::Wrap => {
+            let len = state.memory.len();
+            let overshoot = (amount - state.ptr) % len;
+            state.ptr = if overshoot == 0 { 0 } else { len - overshoot };
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_runs_of_increments() {
+        let instrs = parse("+++").unwrap();
+        assert_eq!(instrs, vec![Instr::Add(3)]);
+    }
+
+    #[test]
+    fn folds_runs_of_moves() {
+        let instrs = parse(">>><").unwrap();
+        assert_eq!(instrs, vec![Instr::Move(2)]);
+    }
+
+    #[test]
+    fn recognizes_set_zero_idiom() {
+        assert_eq!(parse("[-]").unwrap(), vec![Instr::SetZero]);
+        assert_eq!(parse("[+]").unwrap(), vec![Instr::SetZero]);
+    }
+
+    #[test]
+    fn does_not_fold_other_loops_into_set_zero() {
+        let instrs = parse("[->+<]").unwrap();
+        assert_eq!(
+            instrs,
+            vec![Instr::Loop(vec![
+                Instr::Add(-1),
+                Instr::Move(1),
+                Instr::Add(1),
+                Instr::Move(-1),
+            ])]
+        );
+    }
+
+    #[test]
+    fn ignores_comment_characters() {
+        assert_eq!(parse("a+b").unwrap(), vec![Instr::Add(1)]);
+    }
+
+    #[test]
+    fn reports_unmatched_open_bracket() {
+        let err = parse("[+").unwrap_err();
+        assert_eq!(err, "`[`に対応する`]`が見つかりません。");
+    }
+
+    #[test]
+    fn reports_unmatched_close_bracket() {
+        let err = parse("+]").unwrap_err();
+        assert_eq!(err, "`]`に対応する`[`が見つかりません。");
+    }
+
+    #[test]
+    fn display_round_trips_canonical_brainfuck() {
+        let source = "+++>-<[-][->+<].,";
+        let instrs = parse(source).unwrap();
+        let rendered: String = instrs.iter().map(|i| i.to_string()).collect();
+        assert_eq!(rendered, source);
+    }
+
+    #[test]
+    fn display_round_trips_a_fold_of_i8_min_decrements() {
+        // 128 `-` folds to Add(i8::MIN), whose negation overflows plain i8 arithmetic.
+        let source = "-".repeat(128);
+        let instrs = parse(&source).unwrap();
+        assert_eq!(instrs, vec![Instr::Add(i8::MIN)]);
+
+        let rendered: String = instrs.iter().map(|i| i.to_string()).collect();
+        assert_eq!(rendered, source);
+    }
+}
@@ -1,4 +1,11 @@
-use std::{collections::HashMap, str::Chars};
+use std::str::Chars;
+
+mod config;
+mod ir;
+
+pub use config::{Config, Eof, Underflow};
+#[cfg(feature = "parse")]
+pub use ir::{parse, Instr};
 
 #[derive(Debug)]
 pub struct State<'a> {
@@ -8,30 +15,27 @@ pub struct State<'a> {
     input: Chars<'a>,
 }
 
-fn create_bracket_map(programs: &[char]) -> Result<HashMap<usize, usize>, String> {
-    let mut bracket_map: HashMap<usize, usize> = HashMap::new();
-    let mut start_stack: Vec<usize> = vec![];
-
-    for (i, &c) in programs.iter().enumerate() {
-        if c == '[' {
-            start_stack.push(i);
-        } else if c == ']' {
-            let start_index = start_stack
-                .pop()
-                .ok_or("`]`に対応する`[`が見つかりません。")?;
-            bracket_map.insert(start_index, i);
-            bracket_map.insert(i, start_index);
-        }
-    }
-
-    if !start_stack.is_empty() {
-        return Err("`[`に対応する`]`が見つかりません。".into());
+pub fn run<'a>(programs: &'a str, input: &'a str) -> Result<State<'a>, String> {
+    let mut state = State {
+        memory: vec![0; 1024],
+        ptr: 0,
+        output: String::new(),
+        input: input.chars(),
     };
 
-    Ok(bracket_map)
+    let instrs = ir::parse(programs)?;
+    ir::eval(&instrs, &mut state)?;
+
+    Ok(state)
 }
 
-pub fn run<'a>(programs: &'a str, input: &'a str) -> Result<State<'a>, String> {
+/// Like `run`, but lets the caller choose the tape's EOF, underflow, growth
+/// and step-limit behavior via `config` instead of the fixed defaults.
+pub fn run_with_config<'a>(
+    programs: &'a str,
+    input: &'a str,
+    config: &Config,
+) -> Result<State<'a>, String> {
     let mut state = State {
         memory: vec![0; 1024],
         ptr: 0,
@@ -39,46 +43,10 @@ pub fn run<'a>(programs: &'a str, input: &'a str) -> Result<State<'a>, String> {
         input: input.chars(),
     };
 
-    let programs: Vec<char> = programs.chars().collect();
-    let bracket_map = create_bracket_map(&programs)?;
-    // program counter
-    let mut pc: usize = 0;
-
-    while pc < programs.len() {
-        match programs[pc] {
-            '+' => state.memory[state.ptr] = state.memory[state.ptr].wrapping_add(1),
-            '-' => state.memory[state.ptr] = state.memory[state.ptr].wrapping_sub(1),
-            '>' => state.ptr = state.ptr.wrapping_add(1),
-            '<' => {
-                if state.ptr != 0 {
-                    state.ptr = state.ptr.wrapping_sub(1)
-                }
-            }
-            '.' => state.output.push(state.memory[state.ptr].into()),
-            '[' => {
-                if state.memory[state.ptr] == 0 {
-                    pc = *bracket_map
-                        .get(&pc)
-                        .ok_or("`[`に対応する`]`が見つかりません。")?;
-                    continue;
-                }
-            }
-            ']' => {
-                if state.memory[state.ptr] != 0 {
-                    pc = *bracket_map
-                        .get(&pc)
-                        .ok_or("`]`に対応する`[`が見つかりません。")?;
-                    continue;
-                }
-            }
-            ',' => {
-                state.memory[state.ptr] =
-                    state.input.next().ok_or("入力が与えられませんでした。")? as u8;
-            }
-            _ => {}
-        };
-        pc += 1;
-    }
+    let instrs = ir::parse(programs)?;
+    let mut steps = 0;
+    ir::eval_with_config(&instrs, &mut state, config, &mut steps)?;
+
     Ok(state)
 }
 
@@ -260,39 +228,87 @@ mod tests {
     }
 
     #[test]
-    fn test_create_bracket_map_simple() {
-        let m = create_bracket_map(&"[]".chars().collect::<Vec<_>>()).unwrap();
-        assert_eq!(m.get(&0), Some(&1));
-        assert_eq!(m.get(&1), Some(&0));
+    fn test_config_eof_unchanged() {
+        let config = Config {
+            eof: Eof::Unchanged,
+            ..Config::default()
+        };
+        let state = run_with_config("+,", "", &config).unwrap();
+        assert_eq!(state.memory[0], 1);
+    }
+
+    #[test]
+    fn test_config_eof_zero() {
+        let config = Config {
+            eof: Eof::Zero,
+            ..Config::default()
+        };
+        let state = run_with_config("+,", "", &config).unwrap();
+        assert_eq!(state.memory[0], 0);
     }
 
     #[test]
-    fn test_create_bracket_map_nested() {
-        let m = create_bracket_map(&"[[]]".chars().collect::<Vec<_>>()).unwrap();
-        assert_eq!(m.get(&0), Some(&3));
-        assert_eq!(m.get(&1), Some(&2));
-        assert_eq!(m.get(&2), Some(&1));
-        assert_eq!(m.get(&3), Some(&0));
+    fn test_config_eof_negative_one() {
+        let config = Config {
+            eof: Eof::NegativeOne,
+            ..Config::default()
+        };
+        let state = run_with_config(",", "", &config).unwrap();
+        assert_eq!(state.memory[0], 255);
     }
 
     #[test]
-    fn test_create_bracket_map_multiple() {
-        let m = create_bracket_map(&"[][]".chars().collect::<Vec<_>>()).unwrap();
-        assert_eq!(m.get(&0), Some(&1));
-        assert_eq!(m.get(&1), Some(&0));
-        assert_eq!(m.get(&2), Some(&3));
-        assert_eq!(m.get(&3), Some(&2));
+    fn test_config_underflow_error() {
+        let config = Config {
+            underflow: Underflow::Error,
+            ..Config::default()
+        };
+        let result = run_with_config("<", "", &config);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "ポインタが0未満になりました。");
     }
 
     #[test]
-    fn test_create_bracket_map_unmatched_open() {
-        let err = create_bracket_map(&"[[]".chars().collect::<Vec<_>>()).unwrap_err();
-        assert_eq!(err, "`[`に対応する`]`が見つかりません。");
+    fn test_config_underflow_wrap() {
+        let config = Config {
+            underflow: Underflow::Wrap,
+            ..Config::default()
+        };
+        let state = run_with_config("<+", "", &config).unwrap();
+        assert_eq!(state.memory[state.memory.len() - 1], 1);
     }
 
     #[test]
-    fn test_create_bracket_map_unmatched_close() {
-        let err = create_bracket_map(&"[]]".chars().collect::<Vec<_>>()).unwrap_err();
-        assert_eq!(err, "`]`に対応する`[`が見つかりません。");
+    fn test_config_underflow_wrap_multiple_cells() {
+        let config = Config {
+            underflow: Underflow::Wrap,
+            ..Config::default()
+        };
+        let state = run_with_config("<<<+", "", &config).unwrap();
+        assert_eq!(state.memory[state.memory.len() - 3], 1);
+    }
+
+    #[test]
+    fn test_config_growable_tape() {
+        let config = Config {
+            growable_tape: true,
+            ..Config::default()
+        };
+        let program = ">".repeat(2000) + "+";
+        let state = run_with_config(&program, "", &config).unwrap();
+        assert_eq!(state.memory.len(), 2001);
+        assert_eq!(state.memory[2000], 1);
+    }
+
+    #[test]
+    fn test_config_step_limit() {
+        let config = Config {
+            step_limit: Some(5),
+            ..Config::default()
+        };
+        // "+[>+<]" never clears cell 0, so it loops forever without a limit.
+        let result = run_with_config("+[>+<]", "", &config);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), "最大ステップ数に達しました。");
     }
 }